@@ -1,18 +1,66 @@
 use ignore::gitignore::Gitignore;
 use ignore::gitignore::GitignoreBuilder;
+use ignore::gitignore::Glob;
+use ignore::overrides::Override;
+use ignore::overrides::OverrideBuilder;
 use ignore::Match;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Wraps an `ignore` crate error as a [`std::io::Error`], since every public
+/// method in this module reports failures via `std::io::Result`.
+fn io_err(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> std::io::Error {
+    std::io::Error::other(err)
+}
 
 /// Wrapper around the `ignore` crate that loads `.codexignore` patterns and
 /// exposes convenience helpers for matching filesystem paths.
+///
+/// Internally this composes an ordered stack of matchers (`layers`), most
+/// specific first, so that e.g. an explicit extra ignore file can override
+/// the repo's `.gitignore`. [`Self::load_from_root`] is the single-layer
+/// special case; use [`Self::builder`] to compose multiple sources.
 #[derive(Debug, Clone)]
 pub struct CodexIgnore {
     root: PathBuf,
+    layers: Vec<IgnoreLayer>,
+    live: Option<LiveLayer>,
+    overrides: Option<Override>,
+}
+
+/// One matcher in the [`CodexIgnore`] precedence stack, tagged with the
+/// source file it was built from so decisions can be explained.
+#[derive(Debug, Clone)]
+struct IgnoreLayer {
+    source: PathBuf,
     matcher: Arc<Gitignore>,
 }
 
+/// The incrementally-built layer maintained by [`CodexIgnore::add_file`] and
+/// [`CodexIgnore::add_patterns`]. `builder` is retained so more patterns can
+/// be added later; [`CodexIgnore::finish`] drops it to freeze `matcher` for
+/// cheap cloning, though matching works whether or not `finish` was called.
+/// `sources` records every file/pattern added so far so that a builder
+/// dropped by `finish` can be reconstructed (by replaying `sources`) instead
+/// of starting over empty.
+#[derive(Debug, Clone)]
+struct LiveLayer {
+    builder: Option<GitignoreBuilder>,
+    matcher: Arc<Gitignore>,
+    sources: Vec<LiveSource>,
+}
+
+/// One entry previously added to a [`LiveLayer`], kept around so its builder
+/// can be replayed after [`CodexIgnore::finish`] drops it.
+#[derive(Debug, Clone)]
+enum LiveSource {
+    File(PathBuf),
+    Pattern(String),
+}
+
 impl CodexIgnore {
     /// Attempts to load `.codexignore` from `root`. Returns `Ok(None)` when the
     /// file does not exist.
@@ -23,19 +71,127 @@ impl CodexIgnore {
         }
 
         let mut builder = GitignoreBuilder::new(root);
-        builder
-            .add(path)
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-        let matcher = builder
-            .build()
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        if let Some(err) = builder.add(&path) {
+            return Err(io_err(err));
+        }
+        let matcher = builder.build().map_err(io_err)?;
 
         Ok(Some(Self {
             root: root.to_path_buf(),
-            matcher: Arc::new(matcher),
+            layers: vec![IgnoreLayer {
+                source: path,
+                matcher: Arc::new(matcher),
+            }],
+            live: None,
+            overrides: None,
         }))
     }
 
+    /// Creates an empty, incrementally-built matcher rooted at `root`. No
+    /// patterns are active until [`Self::add_file`] or
+    /// [`Self::add_patterns`] is called, which is useful for long-running
+    /// processes that discover ignore files as they scan.
+    pub fn empty(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            layers: Vec::new(),
+            live: Some(LiveLayer {
+                builder: Some(GitignoreBuilder::new(root)),
+                matcher: Arc::new(Gitignore::empty()),
+                sources: Vec::new(),
+            }),
+            overrides: None,
+        }
+    }
+
+    /// Layers caller-supplied override globs (à la ripgrep's `overrides`
+    /// module) that take precedence over every other source: a non-negated
+    /// glob force-includes a path even if `.codexignore` would exclude it,
+    /// while a `!`-prefixed glob force-excludes a path the ignore files
+    /// would otherwise permit.
+    ///
+    /// Sharp edge inherited from [`ignore::overrides::Override`]: once *any*
+    /// non-negated glob is added, the override set stops being purely
+    /// additive. Every file path that matches none of the override globs is
+    /// then reported as ignored too, turning `globs` into a strict allowlist
+    /// for files (directories are unaffected). For example,
+    /// `with_overrides(&["src/**"])` doesn't just force-include `src/**` — it
+    /// also force-ignores every other file, not just the ones `.codexignore`
+    /// would already exclude. Prefer negated-only glob sets (e.g.
+    /// `["!**/*.secret"]`) when the intent is a pure force-exclude/include
+    /// exception list rather than a restriction to a subset of the tree.
+    pub fn with_overrides(mut self, globs: &[&str]) -> std::io::Result<Self> {
+        let mut builder = OverrideBuilder::new(&self.root);
+        for glob in globs {
+            builder.add(glob).map_err(io_err)?;
+        }
+        self.overrides = Some(builder.build().map_err(io_err)?);
+        Ok(self)
+    }
+
+    /// Adds the patterns in the ignore file at `path` to the live layer and
+    /// immediately rebuilds its matcher, so the new patterns take effect for
+    /// any subsequent match.
+    pub fn add_file(&mut self, path: &Path) -> std::io::Result<()> {
+        let root = self.root.clone();
+        let live = self.live.get_or_insert_with(|| LiveLayer {
+            builder: Some(GitignoreBuilder::new(&root)),
+            matcher: Arc::new(Gitignore::empty()),
+            sources: Vec::new(),
+        });
+        if live.builder.is_none() {
+            live.builder = Some(rebuild_live_builder(&root, &live.sources)?);
+        }
+        let builder = live.builder.as_mut().unwrap();
+        if let Some(err) = builder.add(path) {
+            return Err(io_err(err));
+        }
+        live.sources.push(LiveSource::File(path.to_path_buf()));
+        live.matcher = Arc::new(builder.build().map_err(io_err)?);
+        Ok(())
+    }
+
+    /// Adds inline glob patterns to the live layer and immediately rebuilds
+    /// its matcher. Patterns follow `.gitignore` syntax.
+    pub fn add_patterns(&mut self, patterns: &[&str]) -> std::io::Result<()> {
+        let root = self.root.clone();
+        let live = self.live.get_or_insert_with(|| LiveLayer {
+            builder: Some(GitignoreBuilder::new(&root)),
+            matcher: Arc::new(Gitignore::empty()),
+            sources: Vec::new(),
+        });
+        if live.builder.is_none() {
+            live.builder = Some(rebuild_live_builder(&root, &live.sources)?);
+        }
+        let builder = live.builder.as_mut().unwrap();
+        for pattern in patterns {
+            builder.add_line(None, pattern).map_err(io_err)?;
+            live.sources
+                .push(LiveSource::Pattern((*pattern).to_string()));
+        }
+        live.matcher = Arc::new(builder.build().map_err(io_err)?);
+        Ok(())
+    }
+
+    /// Drops the live builder, freezing its matcher so `Self` can be cloned
+    /// and shared across threads cheaply. Matching keeps working whether or
+    /// not `finish` was ever called, and further [`Self::add_file`]/
+    /// [`Self::add_patterns`] calls still layer onto the patterns added
+    /// before `finish` (the builder is transparently reconstructed from the
+    /// live layer's recorded sources); it's purely a performance hint.
+    pub fn finish(&mut self) {
+        if let Some(live) = self.live.as_mut() {
+            live.builder = None;
+        }
+    }
+
+    /// Starts a [`CodexIgnoreBuilder`] for composing multiple layered ignore
+    /// sources (global config, `.gitignore`, `.codexignore`, extra files)
+    /// rooted at `root`.
+    pub fn builder(root: &Path) -> CodexIgnoreBuilder {
+        CodexIgnoreBuilder::new(root)
+    }
+
     /// Returns the project root used to resolve relative paths.
     pub fn root(&self) -> &Path {
         &self.root
@@ -52,15 +208,21 @@ impl CodexIgnore {
     }
 
     /// Returns the path relative to the codexignore root, if possible.
-    pub fn relative_path<'a>(&self, path: &'a Path) -> Option<PathBuf> {
+    pub fn relative_path(&self, path: &Path) -> Option<PathBuf> {
         let abs = self.to_absolute(path);
         abs.strip_prefix(&self.root).map(PathBuf::from).ok()
     }
 
-    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+    /// Explains why `path` was (or wasn't) ignored: which pattern matched,
+    /// in which source file, and on which line. Consults
+    /// [`Self::with_overrides`] first (its decision, if any, is final),
+    /// then the live layer (see [`Self::add_file`]/[`Self::add_patterns`]),
+    /// then the static layers in the same most-specific-first order as
+    /// [`Self::is_file_ignored`].
+    pub fn matched(&self, path: &Path, is_dir: bool) -> IgnoreDecision {
         let abs = self.to_absolute(path);
         let Some(rel) = abs.strip_prefix(&self.root).ok() else {
-            return false;
+            return IgnoreDecision::None;
         };
         let rel = if rel.as_os_str().is_empty() {
             Path::new(".")
@@ -68,9 +230,53 @@ impl CodexIgnore {
             rel
         };
 
-        match self.matcher.matched_path_or_any_parents(rel, is_dir) {
-            Match::Ignore(_) => true,
-            Match::Whitelist(_) | Match::None => false,
+        if let Some(overrides) = &self.overrides {
+            match overrides.matched(rel, is_dir) {
+                Match::Whitelist(_) => {
+                    return IgnoreDecision::Whitelisted {
+                        pattern: "<override>".to_string(),
+                        source: self.root.clone(),
+                        line: 0,
+                    };
+                }
+                Match::Ignore(_) => {
+                    return IgnoreDecision::Ignored {
+                        pattern: "<override>".to_string(),
+                        source: self.root.clone(),
+                        line: 0,
+                    };
+                }
+                Match::None => {}
+            }
+        }
+
+        if let Some(live) = &self.live {
+            let decision = decision_for_match(
+                live.matcher.matched_path_or_any_parents(rel, is_dir),
+                &self.root,
+            );
+            if !matches!(decision, IgnoreDecision::None) {
+                return decision;
+            }
+        }
+
+        for layer in &self.layers {
+            let decision = decision_for_match(
+                layer.matcher.matched_path_or_any_parents(rel, is_dir),
+                &layer.source,
+            );
+            if !matches!(decision, IgnoreDecision::None) {
+                return decision;
+            }
+        }
+
+        IgnoreDecision::None
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        match self.matched(path, is_dir) {
+            IgnoreDecision::Ignored { .. } => true,
+            IgnoreDecision::Whitelisted { .. } | IgnoreDecision::None => false,
         }
     }
 
@@ -81,6 +287,430 @@ impl CodexIgnore {
             self.root.join(path)
         }
     }
+
+    /// Recursively enumerates non-ignored files under `root`, pruning
+    /// ignored directories so their contents are never visited.
+    pub fn walk(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.walk_with(WalkOptions::default())
+    }
+
+    /// Like [`Self::walk`], but with explicit depth and symlink handling.
+    pub fn walk_with(&self, options: WalkOptions) -> impl Iterator<Item = PathBuf> + '_ {
+        WalkIter {
+            ignore: self,
+            options,
+            stack: vec![(self.root.clone(), 0)],
+            current: None,
+            current_depth: 0,
+        }
+    }
+
+    /// Like [`Self::walk`], but visits directories concurrently with rayon.
+    pub fn par_walk(&self) -> Vec<PathBuf> {
+        self.par_walk_with(WalkOptions::default())
+    }
+
+    /// Like [`Self::walk_with`], but visits directories concurrently with
+    /// rayon, forwarding discovered files over a crossbeam channel.
+    pub fn par_walk_with(&self, options: WalkOptions) -> Vec<PathBuf> {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        rayon::scope(|scope| {
+            self.spawn_walk_dir(scope, self.root.clone(), 0, &options, tx);
+        });
+        rx.into_iter().collect()
+    }
+
+    fn spawn_walk_dir<'scope>(
+        &'scope self,
+        scope: &rayon::Scope<'scope>,
+        dir: PathBuf,
+        depth: usize,
+        options: &'scope WalkOptions,
+        tx: crossbeam_channel::Sender<PathBuf>,
+    ) {
+        scope.spawn(move |scope| {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                return;
+            };
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if is_dir_entry(&entry, &path, options.follow_symlinks) {
+                    if self.is_dir_ignored(&path) {
+                        continue;
+                    }
+                    let next_depth = depth + 1;
+                    if options.max_depth.is_none_or(|max| next_depth <= max) {
+                        self.spawn_walk_dir(scope, path, next_depth, options, tx.clone());
+                    }
+                } else if !self.is_file_ignored(&path) {
+                    let _ = tx.send(path);
+                }
+            }
+        });
+    }
+}
+
+/// Reconstructs a [`GitignoreBuilder`] rooted at `root` by replaying every
+/// previously-recorded `sources` entry, used to resume a [`LiveLayer`] whose
+/// builder was dropped by [`CodexIgnore::finish`].
+fn rebuild_live_builder(root: &Path, sources: &[LiveSource]) -> std::io::Result<GitignoreBuilder> {
+    let mut builder = GitignoreBuilder::new(root);
+    for source in sources {
+        match source {
+            LiveSource::File(path) => {
+                if let Some(err) = builder.add(path) {
+                    return Err(io_err(err));
+                }
+            }
+            LiveSource::Pattern(pattern) => {
+                builder.add_line(None, pattern).map_err(io_err)?;
+            }
+        }
+    }
+    Ok(builder)
+}
+
+/// Converts a raw `ignore::Match` into an [`IgnoreDecision`], using
+/// `fallback_source` when the matched glob has no `from()` of its own (e.g.
+/// a pattern added via [`CodexIgnore::add_patterns`]).
+fn decision_for_match(result: Match<&Glob>, fallback_source: &Path) -> IgnoreDecision {
+    match result {
+        Match::Ignore(glob) => IgnoreDecision::Ignored {
+            pattern: glob.original().to_string(),
+            source: glob
+                .from()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| fallback_source.to_path_buf()),
+            line: line_of_pattern(glob.from().unwrap_or(fallback_source), glob.original()),
+        },
+        Match::Whitelist(glob) => IgnoreDecision::Whitelisted {
+            pattern: glob.original().to_string(),
+            source: glob
+                .from()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| fallback_source.to_path_buf()),
+            line: line_of_pattern(glob.from().unwrap_or(fallback_source), glob.original()),
+        },
+        Match::None => IgnoreDecision::None,
+    }
+}
+
+/// Finds the 1-indexed line number of `pattern` within `source`, falling
+/// back to `0` when the file can't be read or the pattern can't be found
+/// (e.g. it was added via [`CodexIgnore::add_patterns`], so `source` is
+/// the root directory rather than an ignore file).
+fn line_of_pattern(source: &Path, pattern: &str) -> usize {
+    let Ok(contents) = std::fs::read_to_string(source) else {
+        return 0;
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.trim_end() == pattern)
+        .map(|(index, _)| index + 1)
+        .unwrap_or(0)
+}
+
+/// Explains the outcome of [`CodexIgnore::matched`]: which pattern decided
+/// the path's fate, which file it came from, and on which line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgnoreDecision {
+    /// A pattern in `source` (at `line`) ignores the path. For an override
+    /// (see [`CodexIgnore::with_overrides`]) `pattern` is `"<override>"`,
+    /// `source` is the root and `line` is `0`.
+    Ignored {
+        pattern: String,
+        source: PathBuf,
+        line: usize,
+    },
+    /// A negated pattern (`!pattern`) in `source` (at `line`) re-includes
+    /// the path after an earlier pattern would have ignored it, or a
+    /// non-negated override glob force-includes it (reported as for
+    /// `Ignored` overrides: `"<override>"`, the root, line `0`).
+    Whitelisted {
+        pattern: String,
+        source: PathBuf,
+        line: usize,
+    },
+    /// No layer's patterns matched the path.
+    None,
+}
+
+/// Depth and symlink controls for [`CodexIgnore::walk`] and
+/// [`CodexIgnore::par_walk`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalkOptions {
+    /// Maximum number of directory levels to descend below `root`. `None`
+    /// means unlimited.
+    pub max_depth: Option<usize>,
+    /// When `true`, symlinked directories are followed and descended into.
+    pub follow_symlinks: bool,
+}
+
+fn is_dir_entry(entry: &std::fs::DirEntry, path: &Path, follow_symlinks: bool) -> bool {
+    if follow_symlinks {
+        path.is_dir()
+    } else {
+        entry.file_type().is_ok_and(|ft| ft.is_dir())
+    }
+}
+
+struct WalkIter<'a> {
+    ignore: &'a CodexIgnore,
+    options: WalkOptions,
+    stack: Vec<(PathBuf, usize)>,
+    current: Option<std::fs::ReadDir>,
+    current_depth: usize,
+}
+
+impl Iterator for WalkIter<'_> {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        loop {
+            if self.current.is_none() {
+                let (dir, depth) = self.stack.pop()?;
+                self.current_depth = depth;
+                self.current = std::fs::read_dir(&dir).ok();
+                if self.current.is_none() {
+                    continue;
+                }
+            }
+
+            let Some(entry) = self.current.as_mut().and_then(Iterator::next) else {
+                self.current = None;
+                continue;
+            };
+            let Ok(entry) = entry else {
+                continue;
+            };
+
+            let path = entry.path();
+            if is_dir_entry(&entry, &path, self.options.follow_symlinks) {
+                if self.ignore.is_dir_ignored(&path) {
+                    continue;
+                }
+                let next_depth = self.current_depth + 1;
+                if self.options.max_depth.is_none_or(|max| next_depth <= max) {
+                    self.stack.push((path, next_depth));
+                }
+            } else if !self.ignore.is_file_ignored(&path) {
+                return Some(path);
+            }
+        }
+    }
+}
+
+/// Builds a [`CodexIgnore`] out of multiple layered ignore sources.
+///
+/// Sources are layered in call order and consulted most-recently-added
+/// first, so a later call (e.g. [`Self::extra_file`]) takes precedence over
+/// an earlier one (e.g. [`Self::global_file`]) when both match the same
+/// path. Call [`Self::no_ignore`] to disable every layer at once, mirroring
+/// watchexec's `--no-ignore`/`--no-vcs-ignore`.
+#[derive(Debug, Clone)]
+pub struct CodexIgnoreBuilder {
+    root: PathBuf,
+    sources: Vec<PathBuf>,
+    no_ignore: bool,
+}
+
+impl CodexIgnoreBuilder {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            sources: Vec::new(),
+            no_ignore: false,
+        }
+    }
+
+    /// Layers a global ignore file, e.g. one loaded from the user's config
+    /// or home directory.
+    pub fn global_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(path.into());
+        self
+    }
+
+    /// Layers the repo's `.gitignore`, if present at `root`.
+    pub fn gitignore(mut self) -> Self {
+        self.sources.push(self.root.join(".gitignore"));
+        self
+    }
+
+    /// Layers the repo's `.codexignore`, if present at `root`.
+    pub fn codexignore(mut self) -> Self {
+        self.sources.push(self.root.join(".codexignore"));
+        self
+    }
+
+    /// Layers an additional caller-supplied ignore file.
+    pub fn extra_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(path.into());
+        self
+    }
+
+    /// When `disable` is `true`, disables every layered source so nothing is
+    /// ever reported as ignored.
+    pub fn no_ignore(mut self, disable: bool) -> Self {
+        self.no_ignore = disable;
+        self
+    }
+
+    /// Builds the composed [`CodexIgnore`]. Missing source files are
+    /// skipped rather than treated as an error.
+    pub fn build(self) -> std::io::Result<CodexIgnore> {
+        let mut layers = Vec::new();
+
+        if !self.no_ignore {
+            for source in &self.sources {
+                if !source.exists() {
+                    continue;
+                }
+
+                let mut builder = GitignoreBuilder::new(&self.root);
+                if let Some(err) = builder.add(source) {
+                    return Err(io_err(err));
+                }
+                let matcher = builder.build().map_err(io_err)?;
+                layers.push(IgnoreLayer {
+                    source: source.clone(),
+                    matcher: Arc::new(matcher),
+                });
+            }
+            layers.reverse();
+        }
+
+        Ok(CodexIgnore {
+            root: self.root,
+            layers,
+            live: None,
+            overrides: None,
+        })
+    }
+}
+
+/// Composes `.codexignore` files discovered at every directory level under
+/// `root`, mirroring ripgrep's `Ignore`/`IgnoreDir` stack: each directory on
+/// the path from `root` down to a candidate file gets its own matcher, and a
+/// nested directory's patterns can override (or be overridden by) its
+/// ancestors.
+///
+/// Matchers are loaded lazily the first time a directory is consulted and
+/// cached in a directory-keyed map, so repeated queries under the same
+/// subtree only pay the parsing cost once.
+#[derive(Debug)]
+pub struct CodexIgnoreTree {
+    root: PathBuf,
+    matchers: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+}
+
+impl CodexIgnoreTree {
+    /// Creates a tree rooted at `root`. No `.codexignore` files are read
+    /// until a path under `root` is actually matched.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            matchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a tree rooted at `root` and eagerly loads `root`'s own
+    /// `.codexignore`, surfacing a parse error immediately instead of on the
+    /// first query. Nested directories are still consulted lazily exactly as
+    /// with [`Self::new`] — this does not restrict matching to `root` alone.
+    pub fn load_from_root(root: &Path) -> std::io::Result<Self> {
+        let tree = Self::new(root);
+        tree.matcher_for_dir(root)?;
+        Ok(tree)
+    }
+
+    /// Returns the project root used to resolve relative paths.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns `true` when the provided path (file) should be ignored.
+    pub fn is_file_ignored(&self, path: &Path) -> bool {
+        self.is_ignored(path, false)
+    }
+
+    /// Returns `true` when the provided directory path should be ignored.
+    pub fn is_dir_ignored(&self, path: &Path) -> bool {
+        self.is_ignored(path, true)
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let abs = self.to_absolute(path);
+        if abs.strip_prefix(&self.root).is_err() {
+            return false;
+        }
+        let Some(start) = abs.parent().filter(|dir| dir.starts_with(&self.root)) else {
+            return false;
+        };
+
+        for dir in Self::ancestors_within_root(start, &self.root) {
+            let Ok(Some(matcher)) = self.matcher_for_dir(&dir) else {
+                continue;
+            };
+            let rel = abs.strip_prefix(&dir).unwrap_or(&abs);
+            match matcher.matched_path_or_any_parents(rel, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => continue,
+            }
+        }
+
+        false
+    }
+
+    /// Returns (and lazily builds) the matcher for `dir`'s own
+    /// `.codexignore`, or `None` when `dir` has no such file.
+    fn matcher_for_dir(&self, dir: &Path) -> std::io::Result<Option<Arc<Gitignore>>> {
+        if let Some(cached) = self.matchers.lock().unwrap().get(dir) {
+            return Ok(cached.clone());
+        }
+
+        let codexignore = dir.join(".codexignore");
+        let matcher = if codexignore.exists() {
+            let mut builder = GitignoreBuilder::new(dir);
+            if let Some(err) = builder.add(&codexignore) {
+                return Err(io_err(err));
+            }
+            let matcher = builder.build().map_err(io_err)?;
+            Some(Arc::new(matcher))
+        } else {
+            None
+        };
+
+        self.matchers
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), matcher.clone());
+        Ok(matcher)
+    }
+
+    /// Yields `start`, then each of its ancestors up to and including `root`
+    /// (deepest first), which is the order matchers must be consulted in.
+    fn ancestors_within_root(start: &Path, root: &Path) -> impl Iterator<Item = PathBuf> {
+        let root = root.to_path_buf();
+        std::iter::successors(Some(start.to_path_buf()), move |dir| {
+            if *dir == root {
+                None
+            } else {
+                dir.parent().map(Path::to_path_buf)
+            }
+        })
+    }
+
+    fn to_absolute(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -100,11 +730,7 @@ mod tests {
     fn matches_files_and_directories() {
         let tmp = TempDir::new().unwrap();
         let root = tmp.path();
-        fs::write(
-            root.join(".codexignore"),
-            "ignored_dir/\nsecret.txt\n",
-        )
-        .unwrap();
+        fs::write(root.join(".codexignore"), "ignored_dir/\nsecret.txt\n").unwrap();
         fs::create_dir_all(root.join("ignored_dir")).unwrap();
 
         let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
@@ -128,4 +754,333 @@ mod tests {
             .unwrap();
         assert_eq!(abs, PathBuf::from("nested").join("file.txt"));
     }
+
+    #[test]
+    fn walk_prunes_ignored_directories() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "ignored_dir/\n.codexignore\n").unwrap();
+        fs::create_dir_all(root.join("ignored_dir")).unwrap();
+        fs::write(root.join("ignored_dir").join("hidden.txt"), "").unwrap();
+        fs::create_dir_all(root.join("kept_dir")).unwrap();
+        fs::write(root.join("kept_dir").join("visible.txt"), "").unwrap();
+        fs::write(root.join("top.txt"), "").unwrap();
+
+        let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
+        let mut files: Vec<PathBuf> = ignore.walk().collect();
+        files.sort();
+
+        assert_eq!(
+            files,
+            vec![
+                root.join("kept_dir").join("visible.txt"),
+                root.join("top.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_with_respects_max_depth() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), ".codexignore\n").unwrap();
+        fs::create_dir_all(root.join("a").join("b")).unwrap();
+        fs::write(root.join("a").join("shallow.txt"), "").unwrap();
+        fs::write(root.join("a").join("b").join("deep.txt"), "").unwrap();
+
+        let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
+        let files: Vec<PathBuf> = ignore
+            .walk_with(WalkOptions {
+                max_depth: Some(1),
+                follow_symlinks: false,
+            })
+            .collect();
+
+        assert_eq!(files, vec![root.join("a").join("shallow.txt")]);
+    }
+
+    #[test]
+    fn tree_composes_nested_codexignore_files() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n").unwrap();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("nested").join(".codexignore"), "!keep.log\n").unwrap();
+
+        let tree = CodexIgnoreTree::new(root);
+        assert!(tree.is_file_ignored(&root.join("top.log")));
+        assert!(tree.is_file_ignored(&root.join("nested").join("other.log")));
+        assert!(!tree.is_file_ignored(&root.join("nested").join("keep.log")));
+    }
+
+    #[test]
+    fn tree_resolves_anchored_patterns_relative_to_their_own_directory() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("nested").join("build")).unwrap();
+        fs::write(root.join("nested").join(".codexignore"), "/build\n").unwrap();
+        fs::create_dir_all(root.join("build")).unwrap();
+
+        let tree = CodexIgnoreTree::new(root);
+        assert!(tree.is_dir_ignored(&root.join("nested").join("build")));
+        assert!(!tree.is_dir_ignored(&root.join("build")));
+    }
+
+    #[test]
+    fn tree_ignores_paths_outside_root_instead_of_walking_past_it() {
+        let tmp = TempDir::new().unwrap();
+        let outer = tmp.path();
+        fs::write(outer.join(".codexignore"), "*.secret\n").unwrap();
+        let root = outer.join("project");
+        fs::create_dir_all(&root).unwrap();
+
+        let tree = CodexIgnoreTree::new(&root);
+        let sibling = outer.join("sibling_file.secret");
+        assert!(!tree.is_file_ignored(&sibling));
+    }
+
+    #[test]
+    fn tree_load_from_root_matches_flat_api() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "secret.txt\n").unwrap();
+
+        let tree = CodexIgnoreTree::load_from_root(root).unwrap();
+        assert!(tree.is_file_ignored(&root.join("secret.txt")));
+        assert!(!tree.is_file_ignored(&root.join("visible.txt")));
+    }
+
+    #[test]
+    fn builder_layers_gitignore_and_codexignore() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root.join(".codexignore"), "*.tmp\n").unwrap();
+
+        let ignore = CodexIgnore::builder(root)
+            .gitignore()
+            .codexignore()
+            .build()
+            .unwrap();
+
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+        assert!(ignore.is_file_ignored(&root.join("scratch.tmp")));
+        assert!(!ignore.is_file_ignored(&root.join("visible.txt")));
+    }
+
+    #[test]
+    fn builder_gives_later_layers_precedence() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n").unwrap();
+        let extra = root.join("extra-ignore");
+        fs::write(&extra, "!debug.log\n").unwrap();
+
+        let ignore = CodexIgnore::builder(root)
+            .codexignore()
+            .extra_file(&extra)
+            .build()
+            .unwrap();
+
+        assert!(!ignore.is_file_ignored(&root.join("debug.log")));
+        assert!(ignore.is_file_ignored(&root.join("other.log")));
+    }
+
+    #[test]
+    fn builder_no_ignore_disables_all_layers() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n").unwrap();
+
+        let ignore = CodexIgnore::builder(root)
+            .codexignore()
+            .no_ignore(true)
+            .build()
+            .unwrap();
+
+        assert!(!ignore.is_file_ignored(&root.join("debug.log")));
+    }
+
+    #[test]
+    fn builder_skips_missing_sources() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+
+        let ignore = CodexIgnore::builder(root)
+            .gitignore()
+            .codexignore()
+            .build()
+            .unwrap();
+
+        assert!(!ignore.is_file_ignored(&root.join("anything.txt")));
+    }
+
+    #[test]
+    fn matched_explains_ignored_path() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(
+            root.join(".codexignore"),
+            "# a comment\n*.txt\nsecret.log\n",
+        )
+        .unwrap();
+        let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
+
+        let decision = ignore.matched(&root.join("secret.log"), false);
+        assert_eq!(
+            decision,
+            IgnoreDecision::Ignored {
+                pattern: "secret.log".to_string(),
+                source: root.join(".codexignore"),
+                line: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn matched_explains_whitelisted_path() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n!keep.log\n").unwrap();
+        let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
+
+        let decision = ignore.matched(&root.join("keep.log"), false);
+        assert_eq!(
+            decision,
+            IgnoreDecision::Whitelisted {
+                pattern: "!keep.log".to_string(),
+                source: root.join(".codexignore"),
+                line: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn matched_returns_none_for_unmatched_path() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n").unwrap();
+        let ignore = CodexIgnore::load_from_root(root).unwrap().unwrap();
+
+        assert_eq!(
+            ignore.matched(&root.join("visible.txt"), false),
+            IgnoreDecision::None
+        );
+    }
+
+    #[test]
+    fn empty_builds_up_patterns_incrementally() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let mut ignore = CodexIgnore::empty(root);
+        assert!(!ignore.is_file_ignored(&root.join("debug.log")));
+
+        ignore.add_patterns(&["*.log"]).unwrap();
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+        assert!(!ignore.is_file_ignored(&root.join("visible.txt")));
+
+        ignore.add_patterns(&["visible.txt"]).unwrap();
+        assert!(ignore.is_file_ignored(&root.join("visible.txt")));
+    }
+
+    #[test]
+    fn empty_add_file_loads_patterns_from_disk() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let extra = root.join("extra-ignore");
+        fs::write(&extra, "*.tmp\n").unwrap();
+
+        let mut ignore = CodexIgnore::empty(root);
+        ignore.add_file(&extra).unwrap();
+
+        assert!(ignore.is_file_ignored(&root.join("scratch.tmp")));
+    }
+
+    #[test]
+    fn matching_works_before_and_after_finish() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let mut ignore = CodexIgnore::empty(root);
+        ignore.add_patterns(&["*.log"]).unwrap();
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+
+        ignore.finish();
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+
+        // Further mutation after `finish` still takes effect, and does not
+        // discard patterns added before `finish` was called.
+        ignore.add_patterns(&["*.tmp"]).unwrap();
+        assert!(ignore.is_file_ignored(&root.join("scratch.tmp")));
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+    }
+
+    #[test]
+    fn add_file_after_finish_preserves_earlier_patterns() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let extra = root.join("extra-ignore");
+        fs::write(&extra, "*.tmp\n").unwrap();
+
+        let mut ignore = CodexIgnore::empty(root);
+        ignore.add_patterns(&["*.log"]).unwrap();
+        ignore.finish();
+
+        ignore.add_file(&extra).unwrap();
+        assert!(ignore.is_file_ignored(&root.join("scratch.tmp")));
+        assert!(ignore.is_file_ignored(&root.join("debug.log")));
+    }
+
+    #[test]
+    fn overrides_force_include_past_codexignore() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.rs\n").unwrap();
+
+        let ignore = CodexIgnore::load_from_root(root)
+            .unwrap()
+            .unwrap()
+            .with_overrides(&["src/**"])
+            .unwrap();
+
+        assert!(!ignore.is_file_ignored(&root.join("src").join("main.rs")));
+    }
+
+    #[test]
+    fn overrides_force_exclude_past_codexignore() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join(".codexignore"), "*.log\n").unwrap();
+
+        let ignore = CodexIgnore::load_from_root(root)
+            .unwrap()
+            .unwrap()
+            .with_overrides(&["!**/*.secret"])
+            .unwrap();
+
+        assert!(ignore.is_file_ignored(&root.join("keys.secret")));
+        assert!(!ignore.is_file_ignored(&root.join("visible.txt")));
+    }
+
+    #[test]
+    fn overrides_with_a_whitelist_glob_become_a_strict_file_allowlist() {
+        // A non-negated override glob isn't purely additive: once one is
+        // present, every file that doesn't match it is reported as ignored
+        // too, even files `.codexignore` never mentioned. This documents
+        // that sharp edge (see `CodexIgnore::with_overrides`) so it doesn't
+        // silently resurface unnoticed.
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src").join("main.rs"), "").unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+
+        let ignore = CodexIgnore::empty(root)
+            .with_overrides(&["src/**"])
+            .unwrap();
+
+        assert!(!ignore.is_file_ignored(&root.join("src").join("main.rs")));
+        assert!(ignore.is_file_ignored(&root.join("README.md")));
+        // Directories are unaffected by the allowlist fallback.
+        assert!(!ignore.is_dir_ignored(&root.join("docs")));
+    }
 }